@@ -1,74 +1,102 @@
+use crate::event::Event;
+use crate::market_stream::{self, Frames, MarketStream};
 use anyhow::Result;
-use fastwebsockets::{handshake, FragmentCollector, OpCode};
-use hyper::header::{CONNECTION, HOST, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION, UPGRADE};
-use hyper::Request;
-use hyper_util::rt::TokioIo;
-use rustls_pki_types::ServerName;
-use std::sync::Arc;
-use tokio::net::TcpStream;
-use tokio_rustls::TlsConnector;
+use serde::Deserialize;
 
-struct SpawnExecutor;
+const PRICE_DECIMALS: u32 = 2;
+const SIZE_DECIMALS: u32 = 8;
 
-impl<Fut> hyper::rt::Executor<Fut> for SpawnExecutor
-where
-    Fut: std::future::Future + Send + 'static,
-    Fut::Output: Send + 'static,
-{
-    fn execute(&self, fut: Fut) {
-        tokio::task::spawn(fut);
+/// Binance's raw per-symbol trade stream (`<symbol>@trade`).
+#[derive(Clone)]
+pub struct BinanceStream {
+    symbol: String,
+}
+
+impl BinanceStream {
+    pub fn new(symbol: String) -> Self {
+        Self { symbol }
     }
 }
 
-pub async fn binance_ws() -> Result<()> {
-    let host = "fstream.binance.com";
-    let path = "/ws/btcusdt@trade";
-    let port = 443;
+impl MarketStream for BinanceStream {
+    async fn connect(&self) -> Result<Frames> {
+        let path = format!("/ws/{}@trade", self.symbol.to_lowercase());
+        market_stream::connect_tls_ws("fstream.binance.com", &path).await
+    }
 
-    let tcp_stream = TcpStream::connect((host, port)).await?;
-    let mut root_store = rustls::RootCertStore::empty();
-    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    fn parse(&self, raw: &[u8]) -> Option<Event> {
+        match serde_json::from_slice::<TradeMessage>(raw) {
+            Ok(msg) => Some(Event::Trade {
+                price: msg.price,
+                size: msg.quantity,
+                timestamp: msg.trade_time,
+            }),
+            Err(e) => {
+                let preview = String::from_utf8_lossy(&raw[..raw.len().min(500)]);
+                eprintln!("Parse error: {}\nRaw: {}", e, preview);
+                None
+            }
+        }
+    }
 
-    let config = rustls::ClientConfig::builder()
-        .with_root_certificates(root_store)
-        .with_no_client_auth();
+    fn subscribe_message(&self) -> String {
+        // The raw `<symbol>@trade` stream URL already scopes the
+        // subscription; no explicit SUBSCRIBE frame is needed.
+        String::new()
+    }
+}
 
-    let connector = TlsConnector::from(Arc::new(config));
-    let domain = ServerName::try_from(host.to_string())?;
-    let tls_stream = connector.connect(domain, tcp_stream).await?;
+#[derive(Debug, Deserialize)]
+struct TradeMessage {
+    #[serde(rename = "p", deserialize_with = "deserialize_price")]
+    price: u64,
+    #[serde(rename = "q", deserialize_with = "deserialize_size")]
+    quantity: u64,
+    #[serde(rename = "T")]
+    trade_time: u64,
+}
 
-    // Double-wrap: TokioIo makes hyper traits work, but we need the inner to have tokio traits
-    // Actually, pass TokioIo<TokioIo<...>> - the inner TokioIo gives tokio traits, outer gives hyper traits
-    let io = TokioIo::new(TokioIo::new(tls_stream));
+/// Deserialize string price like "0.33" to integer 33 (in cents)
+fn deserialize_price<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: &str = Deserialize::deserialize(deserializer)?;
+    parse_decimal_to_int(s, PRICE_DECIMALS).map_err(serde::de::Error::custom)
+}
 
-    let req = Request::builder()
-        .method("GET")
-        .uri(path)
-        .header(HOST, host)
-        .header(UPGRADE, "websocket")
-        .header(CONNECTION, "Upgrade")
-        .header(SEC_WEBSOCKET_KEY, handshake::generate_key())
-        .header(SEC_WEBSOCKET_VERSION, "13")
-        .body(http_body_util::Empty::<bytes::Bytes>::new())?;
+/// Deserialize string quantity like "0.00512345" to integer (in 1e-8 units)
+fn deserialize_size<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: &str = Deserialize::deserialize(deserializer)?;
+    parse_decimal_to_int(s, SIZE_DECIMALS).map_err(serde::de::Error::custom)
+}
 
-    let (ws, _) = handshake::client(&SpawnExecutor, req, io).await?;
-    let mut ws = FragmentCollector::new(ws);
+fn parse_decimal_to_int(s: &str, decimals: u32) -> Result<u64, &'static str> {
+    let multiplier = 10u64.pow(decimals);
 
-    loop {
-        let frame = ws.read_frame().await?;
+    if let Some(dot_pos) = s.find('.') {
+        let int_part: u64 = s[..dot_pos].parse().map_err(|_| "invalid integer part")?;
+        let frac_str = &s[dot_pos + 1..];
+        let frac_len = frac_str.len() as u32;
 
-        match frame.opcode {
-            OpCode::Close => {
-                println!("Connection closed");
-                break;
-            }
-            OpCode::Text => {
-                let raw = &frame.payload[..];
-                println!("{}", String::from_utf8_lossy(raw));
-            }
-            _ => {}
-        }
-    }
+        let frac_part: u64 = frac_str.parse().map_err(|_| "invalid fractional part")?;
 
-    Ok(())
+        // Scale the fractional part to match our precision
+        let scaled_frac = if frac_len < decimals {
+            frac_part * 10u64.pow(decimals - frac_len)
+        } else if frac_len > decimals {
+            frac_part / 10u64.pow(frac_len - decimals)
+        } else {
+            frac_part
+        };
+
+        Ok(int_part * multiplier + scaled_frac)
+    } else {
+        // No decimal point - just an integer
+        let int_part: u64 = s.parse().map_err(|_| "invalid integer")?;
+        Ok(int_part * multiplier)
+    }
 }
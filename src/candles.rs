@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+type PriceLevel = u64;
+type Quantity = u64;
+
+/// Candle bucket width, in seconds.
+pub type Resolution = u64;
+
+pub const ONE_MINUTE: Resolution = 60;
+pub const FIVE_MINUTES: Resolution = 5 * ONE_MINUTE;
+pub const ONE_HOUR: Resolution = 60 * ONE_MINUTE;
+
+/// A single OHLCV bar covering `[open_time, open_time + resolution)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candle {
+    pub open_time: u64,
+    pub open: PriceLevel,
+    pub high: PriceLevel,
+    pub low: PriceLevel,
+    pub close: PriceLevel,
+    pub volume: Quantity,
+}
+
+impl Candle {
+    /// A zero-volume filler candle for a bucket no trade landed in, priced
+    /// flat at the previous candle's close.
+    fn flat(open_time: u64, close: PriceLevel) -> Self {
+        Self {
+            open_time,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 0,
+        }
+    }
+}
+
+/// One resolution's running bucket plus its retained history, for a single
+/// `(market, asset_id)`.
+#[derive(Debug)]
+struct Bucket {
+    resolution: Resolution,
+    current: Option<Candle>,
+    completed: Vec<Candle>,
+}
+
+impl Bucket {
+    fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            current: None,
+            completed: Vec::new(),
+        }
+    }
+
+    fn bucket_start(&self, timestamp: u64) -> u64 {
+        timestamp - (timestamp % self.resolution)
+    }
+
+    /// Fold a trade into this bucket. When the trade's bucket advances past
+    /// the currently open one, the open candle is sealed, any empty
+    /// intervening buckets are filled flat at its close, and a new bucket is
+    /// opened — the sealed candles (oldest first) are returned.
+    fn apply_trade(&mut self, price: PriceLevel, size: Quantity, timestamp: u64) -> Vec<Candle> {
+        let bucket = self.bucket_start(timestamp);
+
+        match &mut self.current {
+            Some(candle) if bucket == candle.open_time => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.volume += size;
+                Vec::new()
+            }
+            // An out-of-order trade landing behind the open bucket is folded
+            // into it rather than rejected, since there's no way to reopen a
+            // bucket that's already sealed.
+            Some(candle) if bucket < candle.open_time => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.volume += size;
+                Vec::new()
+            }
+            Some(candle) => {
+                let sealed_open_time = candle.open_time;
+                let prev_close = candle.close;
+                let mut sealed = vec![self.current.take().unwrap()];
+
+                let mut fill_time = sealed_open_time + self.resolution;
+                while fill_time < bucket {
+                    sealed.push(Candle::flat(fill_time, prev_close));
+                    fill_time += self.resolution;
+                }
+
+                self.current = Some(Candle {
+                    open_time: bucket,
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    volume: size,
+                });
+
+                self.completed.extend(sealed.iter().copied());
+                sealed
+            }
+            None => {
+                self.current = Some(Candle {
+                    open_time: bucket,
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    volume: size,
+                });
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Aggregates `last_trade_price` ticks into OHLCV candles across every
+/// configured [`Resolution`], keyed by `(market, asset_id)` — analogous to
+/// the `/candles` route in openbook-candles.
+#[derive(Debug)]
+pub struct CandleStore {
+    resolutions: Vec<Resolution>,
+    buckets: HashMap<(String, String), HashMap<Resolution, Bucket>>,
+}
+
+impl CandleStore {
+    pub fn new(resolutions: Vec<Resolution>) -> Self {
+        Self {
+            resolutions,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Fold a trade into every configured resolution's bucket for
+    /// `(market, asset_id)`. Returns `(resolution, candle)` for every candle
+    /// sealed as a result.
+    pub fn apply_trade(
+        &mut self,
+        market: &str,
+        asset_id: &str,
+        price: PriceLevel,
+        size: Quantity,
+        timestamp: u64,
+    ) -> Vec<(Resolution, Candle)> {
+        let by_resolution = self
+            .buckets
+            .entry((market.to_string(), asset_id.to_string()))
+            .or_default();
+
+        let mut sealed = Vec::new();
+        for &resolution in &self.resolutions {
+            let bucket = by_resolution
+                .entry(resolution)
+                .or_insert_with(|| Bucket::new(resolution));
+            sealed.extend(
+                bucket
+                    .apply_trade(price, size, timestamp)
+                    .into_iter()
+                    .map(|candle| (resolution, candle)),
+            );
+        }
+        sealed
+    }
+
+    /// Retained candles for `(market, asset)` at `resolution` whose
+    /// `open_time` falls in `[from, to)`, oldest first. Includes the
+    /// still-open current bucket if it's in range.
+    pub fn candles(
+        &self,
+        market: &str,
+        asset: &str,
+        from: u64,
+        to: u64,
+        resolution: Resolution,
+    ) -> Vec<Candle> {
+        let Some(bucket) = self
+            .buckets
+            .get(&(market.to_string(), asset.to_string()))
+            .and_then(|by_resolution| by_resolution.get(&resolution))
+        else {
+            return Vec::new();
+        };
+
+        bucket
+            .completed
+            .iter()
+            .chain(bucket.current.iter())
+            .filter(|candle| candle.open_time >= from && candle.open_time < to)
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A trade landing several buckets past the open one must seal it, fill
+    /// every empty bucket in between flat at its close with zero volume,
+    /// and only then open the new bucket — an off-by-one here would either
+    /// drop a bucket or leave a gap in retained history.
+    #[test]
+    fn apply_trade_fills_flat_candles_for_skipped_buckets() {
+        let mut store = CandleStore::new(vec![ONE_MINUTE]);
+
+        let sealed = store.apply_trade("market-1", "asset-1", 100, 5, 10);
+        assert!(sealed.is_empty());
+
+        // Three buckets (0s, 60s, 120s) elapse before this trade at 190s,
+        // which opens the 180s bucket.
+        let sealed = store.apply_trade("market-1", "asset-1", 110, 3, 190);
+        assert_eq!(sealed.len(), 3);
+
+        let (resolution, first) = sealed[0];
+        assert_eq!(resolution, ONE_MINUTE);
+        assert_eq!(first.open_time, 0);
+        assert_eq!(first.close, 100);
+        assert_eq!(first.volume, 5);
+
+        let (_, flat_60) = sealed[1];
+        assert_eq!(flat_60.open_time, 60);
+        assert_eq!(flat_60.open, 100);
+        assert_eq!(flat_60.high, 100);
+        assert_eq!(flat_60.low, 100);
+        assert_eq!(flat_60.close, 100);
+        assert_eq!(flat_60.volume, 0);
+
+        let (_, flat_120) = sealed[2];
+        assert_eq!(flat_120.open_time, 120);
+        assert_eq!(flat_120.close, 100);
+        assert_eq!(flat_120.volume, 0);
+
+        let candles = store.candles("market-1", "asset-1", 0, 200, ONE_MINUTE);
+        assert_eq!(
+            candles.iter().map(|c| c.open_time).collect::<Vec<_>>(),
+            vec![0, 60, 120, 180]
+        );
+        assert_eq!(candles[3].open, 110);
+        assert_eq!(candles[3].volume, 3);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::orderbook::Orderbook;
+use crate::polymarket::messages::price_change::PriceChange;
+
+type PriceLevel = u64;
+type Quantity = u64;
+
+/// A single parsed market feed event — the shared payload produced by
+/// websocket parsing and aggregate handling, and the unit recorded/replayed
+/// by [`crate::recorder`].
+#[derive(Debug, Clone)]
+pub enum Event {
+    Snapshot(Orderbook),
+    PriceChange {
+        changes: Vec<PriceChange>,
+        hash: u32,
+        timestamp: u64,
+    },
+    Trade {
+        price: PriceLevel,
+        size: Quantity,
+        timestamp: u64,
+    },
+}
+
+impl Event {
+    /// The event's own timestamp, used as the recorded-log header field.
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            Event::Snapshot(ob) => ob.timestamp,
+            Event::PriceChange { timestamp, .. } => *timestamp,
+            Event::Trade { timestamp, .. } => *timestamp,
+        }
+    }
+}
@@ -6,8 +6,12 @@ use reqwest::Client;
 use std::sync::Arc;
 
 mod binance_ws;
+mod candles;
+mod event;
+mod market_stream;
 mod orderbook;
 mod polymarket;
+mod recorder;
 
 use orderbook::Orderbook;
 
@@ -44,10 +48,11 @@ async fn main() -> Result<()> {
     // // Create empty orderbook
     // let orderbook = Arc::new(RwLock::new(Orderbook::default()));
 
-    // // Spawn polymarket websocket as background task
+    // // Spawn polymarket stream as background task; it reconnects on its own
+    // let asset_id = "52286616472996634577443051031708917634646051347292466975337196584207785187680".to_string();
     // let polymarket_handle = tokio::spawn(async move {
-    //     if let Err(e) = polymarket::websocket::connect(orderbook).await {
-    //         eprintln!("Polymarket websocket error: {}", e);
+    //     if let Err(e) = polymarket::stream::spawn(asset_id, orderbook, None).await {
+    //         eprintln!("Polymarket stream error: {}", e);
     //     }
     // });
 
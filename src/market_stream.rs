@@ -0,0 +1,208 @@
+use crate::event::Event;
+use crate::SpawnExecutor;
+use anyhow::Result;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use fastwebsockets::{handshake, FragmentCollector, Frame, OpCode, Payload};
+use hyper::header::{CONNECTION, HOST, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION, UPGRADE};
+use hyper::Request;
+use hyper_util::rt::TokioIo;
+use rayon::ThreadPoolBuilder;
+use rustls_pki_types::ServerName;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::TlsConnector;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const PARSE_POOL_THREADS: usize = 4;
+
+/// A frame parsed off the wire, still tagged with its position in the read
+/// order so [`reorder_and_forward`] can put same-connection frames back in
+/// sequence after they race through the parse pool.
+struct ParsedFrame {
+    seq_no: u64,
+    event: Option<Event>,
+}
+
+/// A connected, fragment-reassembling websocket, common to every
+/// [`MarketStream`] since each one is a plain TLS websocket underneath.
+pub type Frames = FragmentCollector<TokioIo<TokioIo<TlsStream<TcpStream>>>>;
+
+/// A market data feed that can be driven by the generic [`run`] loop.
+///
+/// Implementors only need to know how to open their connection, how to
+/// subscribe, and how to turn a raw text frame into an [`Event`] — the
+/// read loop, parse-pool offload + reorder, ping/pong handling, and
+/// reconnect/backoff behavior are shared. [`run`] clones the stream onto the
+/// parse pool for every frame, so implementations should stay cheap to
+/// clone (e.g. a subscribed symbol/asset id).
+pub trait MarketStream {
+    /// Open a fresh TLS websocket connection and complete the handshake.
+    async fn connect(&self) -> Result<Frames>;
+
+    /// Parse a raw text frame into a market [`Event`], if recognized.
+    fn parse(&self, raw: &[u8]) -> Option<Event>;
+
+    /// The message to send right after each (re)connect. An empty string
+    /// means the feed needs no explicit subscribe step.
+    fn subscribe_message(&self) -> String;
+}
+
+/// Open a TLS connection to `host:443` and complete the websocket handshake
+/// at `path`. Shared by every [`MarketStream::connect`] implementation,
+/// since the TLS + handshake + [`FragmentCollector`] setup is identical
+/// across feeds — only the host and path differ.
+pub async fn connect_tls_ws(host: &str, path: &str) -> Result<Frames> {
+    let tcp_stream = TcpStream::connect((host, 443)).await?;
+    let mut root_store = rustls::RootCertStore::empty();
+    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    let connector = TlsConnector::from(Arc::new(config));
+    let domain = ServerName::try_from(host.to_string())?;
+    let tls_stream = connector.connect(domain, tcp_stream).await?;
+    let io = TokioIo::new(TokioIo::new(tls_stream));
+
+    let req = Request::builder()
+        .method("GET")
+        .uri(path)
+        .header(HOST, host)
+        .header(UPGRADE, "websocket")
+        .header(CONNECTION, "Upgrade")
+        .header(SEC_WEBSOCKET_KEY, handshake::generate_key())
+        .header(SEC_WEBSOCKET_VERSION, "13")
+        .body(http_body_util::Empty::<bytes::Bytes>::new())?;
+
+    let (ws, _) = handshake::client(&SpawnExecutor, req, io).await?;
+    Ok(FragmentCollector::new(ws))
+}
+
+/// Drives a [`MarketStream`] indefinitely (it never returns): connects, subscribes, reads
+/// frames and forwards parsed [`Event`]s on `tx`, replies to pings, and on
+/// close or error reconnects with exponential backoff — automatically
+/// re-sending the subscribe message (which also re-requests a fresh
+/// snapshot) once the new connection is up. `resubscribe_rx` lets a
+/// consumer ask for the subscribe message to be re-sent on the *current*
+/// connection, e.g. after detecting a checksum desync that doesn't warrant
+/// a full reconnect.
+pub async fn run<S: MarketStream + Clone + Send + Sync + 'static>(
+    stream: &S,
+    tx: Sender<Event>,
+    resubscribe_rx: Receiver<()>,
+) -> Result<()> {
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        let mut ws = match stream.connect().await {
+            Ok(ws) => ws,
+            Err(e) => {
+                eprintln!("Connect failed: {e}. Retrying in {backoff:?}");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                continue;
+            }
+        };
+
+        if let Err(e) = send_subscribe(&mut ws, stream).await {
+            eprintln!("Subscribe failed: {e}. Reconnecting in {backoff:?}");
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            continue;
+        }
+        backoff = INITIAL_BACKOFF;
+
+        if let Err(e) = read_loop(&mut ws, stream, &tx, &resubscribe_rx).await {
+            eprintln!("Connection lost: {e}. Reconnecting in {backoff:?}");
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
+
+async fn send_subscribe<S: MarketStream>(ws: &mut Frames, stream: &S) -> Result<()> {
+    let subscribe_message = stream.subscribe_message();
+    if subscribe_message.is_empty() {
+        return Ok(());
+    }
+    ws.write_frame(Frame::text(Payload::Owned(subscribe_message.into_bytes())))
+        .await?;
+    Ok(())
+}
+
+async fn read_loop<S: MarketStream + Clone + Send + Sync + 'static>(
+    ws: &mut Frames,
+    stream: &S,
+    tx: &Sender<Event>,
+    resubscribe_rx: &Receiver<()>,
+) -> Result<()> {
+    // Parsing runs on a rayon pool rather than inline on this task, so a
+    // slow or bursty batch of frames can't stall reading (and, critically,
+    // replying to Ping) on the connection. Frames race through the pool out
+    // of order, so each one is tagged with its read-order seq_no and handed
+    // to a dedicated thread that buffers them in a BTreeMap and forwards to
+    // `tx` strictly in order.
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(PARSE_POOL_THREADS)
+        .build()?;
+    let (parsed_tx, parsed_rx) = unbounded::<ParsedFrame>();
+    let reorder_tx = tx.clone();
+    std::thread::spawn(move || reorder_and_forward(parsed_rx, reorder_tx));
+
+    let mut seq_no: u64 = 0;
+
+    loop {
+        if resubscribe_rx.try_recv().is_ok() {
+            eprintln!("🔄 Resubscribing on existing connection");
+            send_subscribe(ws, stream).await?;
+        }
+
+        let frame = ws.read_frame().await?;
+
+        match frame.opcode {
+            // Treated the same as any other dropped connection so `run`'s
+            // backoff branch (and not a zero-delay reconnect) handles it —
+            // a server that's rate-limiting or force-disconnecting us would
+            // otherwise get hammered with immediate reconnect attempts.
+            OpCode::Close => anyhow::bail!("connection closed by peer"),
+            OpCode::Ping => {
+                ws.write_frame(Frame::pong(frame.payload)).await?;
+            }
+            OpCode::Text => {
+                let seq = seq_no;
+                seq_no += 1;
+
+                let stream = stream.clone();
+                let raw = frame.payload.to_vec();
+                let parsed_tx = parsed_tx.clone();
+                pool.spawn(move || {
+                    let event = stream.parse(&raw);
+                    let _ = parsed_tx.send(ParsedFrame { seq_no: seq, event });
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Buffers [`ParsedFrame`]s by `seq_no` and forwards them to `tx` strictly
+/// in read order, exactly like the sequenced reordering buffer the
+/// pre-unification Polymarket feed used downstream of its parse pool.
+fn reorder_and_forward(parsed_rx: Receiver<ParsedFrame>, tx: Sender<Event>) {
+    let mut buffer = BTreeMap::new();
+    let mut next_seq: u64 = 0;
+
+    while let Ok(frame) = parsed_rx.recv() {
+        buffer.insert(frame.seq_no, frame.event);
+
+        while let Some(event) = buffer.remove(&next_seq) {
+            if let Some(event) = event {
+                let _ = tx.send(event);
+            }
+            next_seq += 1;
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use crate::polymarket::messages::price_change::{PriceChange, Side};
 use parking_lot::RwLock;
 use serde::Deserialize;
 use std::cmp::Reverse;
 use std::collections::BTreeMap;
+use std::fmt;
 
 type PriceLevel = u64;
 type Quantity = u64;
@@ -25,19 +27,42 @@ struct IncomingOrderBookMessage {
     timestamp: u64,
     bids: Vec<LevelEntry>,
     asks: Vec<LevelEntry>,
-    #[allow(dead_code)]
-    hash: String,
+    #[serde(deserialize_with = "deserialize_hash")]
+    hash: u32,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Orderbook {
     pub market: String,
     pub asset_id: String,
     pub timestamp: u64,
     pub asks: BTreeMap<PriceLevel, Quantity>,
     pub bids: BTreeMap<Reverse<PriceLevel>, Quantity>,
+    /// Last server-provided checksum, used to detect desync via [`Orderbook::verify_checksum`]
+    pub hash: u32,
 }
 
+/// Returned by [`Orderbook::verify_checksum`] when the locally maintained book
+/// diverges from the server-provided hash, meaning it must be resynced from a
+/// fresh snapshot rather than served as-is.
+#[derive(Debug)]
+pub struct ChecksumMismatch {
+    pub expected: u32,
+    pub actual: u32,
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "orderbook checksum mismatch: expected {:#010x}, computed {:#010x}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
 impl Orderbook {
     pub fn apply_snapshot(&mut self, snapshot: Orderbook) {
         self.market = snapshot.market;
@@ -45,6 +70,15 @@ impl Orderbook {
         self.timestamp = snapshot.timestamp;
         self.asks = snapshot.asks;
         self.bids = snapshot.bids;
+        self.hash = snapshot.hash;
+    }
+
+    /// Reset to an empty book, dropping any known-good checksum, ahead of a
+    /// fresh snapshot resubscribe.
+    pub fn clear(&mut self) {
+        self.asks.clear();
+        self.bids.clear();
+        self.hash = 0;
     }
     pub fn update_from_bytes(&mut self, bytes: &[u8]) -> Result<(), serde_json::Error> {
         let snapshot = Orderbook::from_bytes(bytes)?;
@@ -52,7 +86,35 @@ impl Orderbook {
         Ok(())
     }
 
-    
+    /// Apply incremental `price_change` deltas on top of the current book.
+    ///
+    /// Changes for assets other than `self.asset_id` are ignored. A size of
+    /// zero removes the level entirely, mirroring the `filter(|e| e.size > 0)`
+    /// invariant applied to snapshot levels.
+    pub fn apply_price_changes(&mut self, changes: &[PriceChange]) {
+        for change in changes {
+            if change.asset_id != self.asset_id {
+                continue;
+            }
+
+            match change.side {
+                Side::Buy => {
+                    if change.size == 0 {
+                        self.bids.remove(&Reverse(change.price));
+                    } else {
+                        self.bids.insert(Reverse(change.price), change.size);
+                    }
+                }
+                Side::Sell => {
+                    if change.size == 0 {
+                        self.asks.remove(&change.price);
+                    } else {
+                        self.asks.insert(change.price, change.size);
+                    }
+                }
+            }
+        }
+    }
 
     // Helper function
     pub fn from_bytes(bytes: &[u8]) -> Result<Orderbook, serde_json::Error> {
@@ -78,9 +140,50 @@ impl Orderbook {
             timestamp: msg.timestamp,
             asks,
             bids,
+            hash: msg.hash,
         })
     }
 
+    /// CRC32 over the top 25 levels, interleaving best bid and best ask as
+    /// `price:size:price:size:...` and skipping a side once its levels run
+    /// out, mirroring the OKX L2 checksum scheme.
+    pub fn checksum(&self) -> u32 {
+        let mut payload = String::new();
+        let mut bids = self.bids.iter();
+        let mut asks = self.asks.iter();
+
+        for _ in 0..25 {
+            let bid = bids.next();
+            let ask = asks.next();
+            if bid.is_none() && ask.is_none() {
+                break;
+            }
+            if let Some((Reverse(price), size)) = bid {
+                push_level(&mut payload, *price, *size);
+            }
+            if let Some((price, size)) = ask {
+                push_level(&mut payload, *price, *size);
+            }
+        }
+
+        crc32(payload.as_bytes())
+    }
+
+    /// Compare the locally computed checksum against the last server-provided
+    /// hash, surfacing any desync as a recoverable error rather than silently
+    /// continuing to serve a corrupt book.
+    pub fn verify_checksum(&self) -> Result<(), ChecksumMismatch> {
+        let actual = self.checksum();
+        if actual == self.hash {
+            Ok(())
+        } else {
+            Err(ChecksumMismatch {
+                expected: self.hash,
+                actual,
+            })
+        }
+    }
+
     /// Get the best bid (highest price willing to buy)
     #[inline]
     pub fn best_bid(&self) -> Option<(PriceLevel, Quantity)> {
@@ -110,6 +213,86 @@ impl Orderbook {
             _ => None,
         }
     }
+
+    /// Get the top `levels` bids and asks, each in book order (best first).
+    pub fn depth(
+        &self,
+        levels: usize,
+    ) -> (Vec<(PriceLevel, Quantity)>, Vec<(PriceLevel, Quantity)>) {
+        let bids = self
+            .bids
+            .iter()
+            .take(levels)
+            .map(|(k, &v)| (k.0, v))
+            .collect();
+        let asks = self
+            .asks
+            .iter()
+            .take(levels)
+            .map(|(&k, &v)| (k, v))
+            .collect();
+        (bids, asks)
+    }
+
+    /// Sum the size available between the best price on `side` and
+    /// `up_to_price` (inclusive).
+    pub fn cumulative_depth(&self, side: Side, up_to_price: PriceLevel) -> Quantity {
+        match side {
+            Side::Buy => self
+                .bids
+                .iter()
+                .filter(|entry| entry.0 .0 >= up_to_price)
+                .map(|(_, &qty)| qty)
+                .sum(),
+            Side::Sell => self
+                .asks
+                .iter()
+                .filter(|entry| *entry.0 <= up_to_price)
+                .map(|(_, &qty)| qty)
+                .sum(),
+        }
+    }
+
+    /// The size-weighted average price to fill `target_qty` by walking the
+    /// book from the best price on `side` outward, or `None` if the book
+    /// can't fill the requested quantity.
+    pub fn vwap_for_size(&self, side: Side, target_qty: Quantity) -> Option<PriceLevel> {
+        if target_qty == 0 {
+            return None;
+        }
+
+        let mut remaining = target_qty;
+        let mut cost: u128 = 0;
+
+        match side {
+            Side::Buy => {
+                for (&Reverse(price), &qty) in self.bids.iter() {
+                    if remaining == 0 {
+                        break;
+                    }
+                    let take = remaining.min(qty);
+                    cost += price as u128 * take as u128;
+                    remaining -= take;
+                }
+            }
+            Side::Sell => {
+                for (&price, &qty) in self.asks.iter() {
+                    if remaining == 0 {
+                        break;
+                    }
+                    let take = remaining.min(qty);
+                    cost += price as u128 * take as u128;
+                    remaining -= take;
+                }
+            }
+        }
+
+        if remaining > 0 {
+            None
+        } else {
+            Some((cost / target_qty as u128) as PriceLevel)
+        }
+    }
 }
 
 /// Deserialize string price like "0.33" to integer 33 (in cents)
@@ -139,6 +322,53 @@ where
     s.parse::<u64>().map_err(serde::de::Error::custom)
 }
 
+/// Deserialize a hex-encoded checksum string like "a1b2c3d4" to its u32 value
+fn deserialize_hash<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: &str = Deserialize::deserialize(deserializer)?;
+    u32::from_str_radix(s, 16).map_err(serde::de::Error::custom)
+}
+
+/// Render a fixed-point integer back to its decimal string form
+/// e.g., 33 with 2 decimals -> "0.33"
+/// e.g., 41425 with 1 decimal -> "4142.5"
+fn format_decimal(value: u64, decimals: u32) -> String {
+    if decimals == 0 {
+        return value.to_string();
+    }
+    let multiplier = 10u64.pow(decimals);
+    let int_part = value / multiplier;
+    let frac_part = value % multiplier;
+    format!("{int_part}.{frac_part:0width$}", width = decimals as usize)
+}
+
+/// Append a `price:size` level (prefixed with `:` if the buffer is non-empty)
+fn push_level(buf: &mut String, price: PriceLevel, size: Quantity) {
+    if !buf.is_empty() {
+        buf.push(':');
+    }
+    buf.push_str(&format_decimal(price, PRICE_DECIMALS));
+    buf.push(':');
+    buf.push_str(&format_decimal(size, SIZE_DECIMALS));
+}
+
+/// CRC32 (IEEE 802.3), computed bit-by-bit to avoid pulling in a dependency
+/// for a single hash function
+fn crc32(bytes: &[u8]) -> u32 {
+    const POLY: u32 = 0xEDB88320;
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (POLY & mask);
+        }
+    }
+    !crc
+}
+
 /// Parse a decimal string to integer with fixed precision
 /// e.g., "0.33" with 2 decimals -> 33
 /// e.g., "4142.5" with 1 decimal -> 41425
@@ -168,3 +398,28 @@ fn parse_decimal_to_int(s: &str, decimals: u32) -> Result<u64, &'static str> {
         Ok(int_part * multiplier)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `hash` is documented by Polymarket as a hex-encoded checksum string;
+    /// pin that assumption against a literal sample message so a change in
+    /// wire encoding (e.g. to a decimal string) fails loudly here instead of
+    /// silently breaking every [`Orderbook::verify_checksum`] comparison.
+    #[test]
+    fn deserialize_hash_reads_hex_encoded_checksum() {
+        let sample = br#"{
+            "market": "0xmarket",
+            "asset_id": "123",
+            "timestamp": "1700000000",
+            "bids": [{"price": "0.50", "size": "10.0"}],
+            "asks": [{"price": "0.51", "size": "10.0"}],
+            "hash": "a1b2c3d4"
+        }"#;
+
+        let ob = Orderbook::from_bytes(sample).expect("valid sample message");
+
+        assert_eq!(ob.hash, 0xa1b2c3d4);
+    }
+}
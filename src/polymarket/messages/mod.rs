@@ -0,0 +1,2 @@
+pub mod last_trade_price;
+pub mod price_change;
@@ -7,20 +7,40 @@ const PRICE_DECIMALS: u32 = 2;
 const SIZE_DECIMALS: u32 = 1;
 
 #[derive(Debug, Deserialize)]
-struct IncomingPriceChangeMessage {
-    market: String,
-    price_changes: Vec<PriceChange>,
+pub struct IncomingPriceChangeMessage {
+    pub market: String,
+    pub price_changes: Vec<PriceChange>,
     #[serde(deserialize_with = "deserialize_timestamp")]
-    timestamp: u64,
+    pub timestamp: u64,
+    /// Checksum of the book state resulting from these changes, verified via
+    /// `Orderbook::verify_checksum` after they're applied
+    #[serde(deserialize_with = "deserialize_hash")]
+    pub hash: u32,
 }
 
-#[derive(Debug, Deserialize)]
-struct PriceChange {
-    asset_id: String,
+impl IncomingPriceChangeMessage {
+    // Helper function
+    pub fn from_bytes(bytes: &[u8]) -> Result<IncomingPriceChangeMessage, serde_json::Error> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceChange {
+    pub asset_id: String,
+    pub side: Side,
     #[serde(deserialize_with = "deserialize_price")]
-    price: PriceLevel,
+    pub price: PriceLevel,
     #[serde(deserialize_with = "deserialize_size")]
-    size: Quantity,
+    pub size: Quantity,
+}
+
+/// Which side of the book a `PriceChange` applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Side {
+    Buy,
+    Sell,
 }
 
 pub struct UpdateBook {
@@ -56,6 +76,15 @@ where
     s.parse::<u64>().map_err(serde::de::Error::custom)
 }
 
+/// Deserialize a hex-encoded checksum string like "a1b2c3d4" to its u32 value
+fn deserialize_hash<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: &str = Deserialize::deserialize(deserializer)?;
+    u32::from_str_radix(s, 16).map_err(serde::de::Error::custom)
+}
+
 fn parse_decimal_to_int(s: &str, decimals: u32) -> Result<u64, &'static str> {
     let multiplier = 10u64.pow(decimals);
 
@@ -0,0 +1,3 @@
+pub mod gamma;
+pub mod messages;
+pub mod stream;
@@ -0,0 +1,308 @@
+use crate::candles::{Candle, CandleStore, Resolution, FIVE_MINUTES, ONE_HOUR, ONE_MINUTE};
+use crate::event::Event;
+use crate::market_stream::{self, Frames, MarketStream};
+use crate::orderbook::Orderbook;
+use crate::polymarket::messages::last_trade_price::IncomingLastTradePriceMessage;
+use crate::polymarket::messages::price_change::IncomingPriceChangeMessage;
+use crate::recorder::{Recorder, Replayer};
+use anyhow::Result;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const LAST_TRADE_PRICE: &[u8] = b"event_type\":\"last_trade_price\"";
+const PRICE_CHANGE: &[u8] = b"event_type\":\"price_change\"";
+const ORDERBOOK: &[u8] = b"event_type\":\"book\"";
+
+/// Resolutions maintained for every `(market, asset_id)` we stream.
+const CANDLE_RESOLUTIONS: [Resolution; 3] = [ONE_MINUTE, FIVE_MINUTES, ONE_HOUR];
+
+/// Polymarket's CLOB market-data websocket feed for a single asset.
+#[derive(Clone)]
+pub struct PolymarketStream {
+    asset_id: String,
+}
+
+impl PolymarketStream {
+    pub fn new(asset_id: String) -> Self {
+        Self { asset_id }
+    }
+}
+
+impl MarketStream for PolymarketStream {
+    async fn connect(&self) -> Result<Frames> {
+        market_stream::connect_tls_ws("ws-subscriptions-clob.polymarket.com", "/ws/market").await
+    }
+
+    fn parse(&self, raw: &[u8]) -> Option<Event> {
+        if is_price_change_message(raw) {
+            parse_price_change(raw)
+        } else if is_last_trade_price_message(raw) {
+            parse_trade(raw)
+        } else if is_book_message(raw) {
+            // A "book" event pushed outside of the initial array snapshot
+            // is a full resend for an asset we're not (re-)subscribing to
+            // right now; nothing to apply yet.
+            None
+        } else {
+            parse_snapshot(strip_array_wrapper(raw)).map(Event::Snapshot)
+        }
+    }
+
+    fn subscribe_message(&self) -> String {
+        format!(
+            r#"{{"type": "market", "assets_ids": ["{}"]}}"#,
+            self.asset_id
+        )
+    }
+}
+
+/// Connects to Polymarket's market feed for `asset_id` and applies every
+/// event to `orderbook`, reconnecting automatically on disconnect. When
+/// `record_path` is set, every event is also appended to a binary log via
+/// [`Recorder`] before being applied, so the session can later be fed back
+/// through [`replay`].
+pub async fn spawn(
+    asset_id: String,
+    orderbook: Arc<RwLock<Orderbook>>,
+    record_path: Option<PathBuf>,
+) -> Result<()> {
+    let stream = PolymarketStream::new(asset_id.clone());
+    let recorder = record_path.map(Recorder::open).transpose()?;
+
+    let (tx, rx) = unbounded::<Event>();
+    let (resubscribe_tx, resubscribe_rx) = unbounded::<()>();
+    std::thread::spawn(move || handle_events(rx, orderbook, resubscribe_tx, asset_id, recorder));
+
+    market_stream::run(&stream, tx, resubscribe_rx).await
+}
+
+/// Replays a binary log written by [`Recorder`] back through the same
+/// parse/aggregate pipeline [`handle_events`] drives for a live socket,
+/// returning the resulting [`Orderbook`] and [`CandleStore`] once the log is
+/// exhausted.
+pub fn replay(path: impl AsRef<Path>, asset_id: &str) -> Result<(Orderbook, CandleStore)> {
+    let replayer = Replayer::open(path)?;
+
+    let mut orderbook = Orderbook::default();
+    let mut candles = CandleStore::new(CANDLE_RESOLUTIONS.to_vec());
+    let mut have_snapshot = false;
+
+    for (seq_no, event) in replayer.iter().enumerate() {
+        let desynced = apply_event(
+            event,
+            &mut orderbook,
+            &mut candles,
+            asset_id,
+            seq_no as u64,
+            &mut have_snapshot,
+        );
+        if desynced {
+            // There's no live connection to resubscribe on replay — the
+            // recorded log is the only source of truth, so note the desync
+            // and keep draining it rather than stalling.
+            eprintln!("🔴 Desync detected replaying record #{seq_no}; continuing from the log");
+        }
+    }
+
+    Ok((orderbook, candles))
+}
+
+// WE want to avoid orderbook messages (unless it's the initial book)
+fn is_book_message(raw: &[u8]) -> bool {
+    raw.first() != Some(&b'[') && raw.windows(ORDERBOOK.len()).any(|w| w == ORDERBOOK)
+}
+
+fn is_price_change_message(raw: &[u8]) -> bool {
+    raw.windows(PRICE_CHANGE.len()).any(|w| w == PRICE_CHANGE)
+}
+
+fn is_last_trade_price_message(raw: &[u8]) -> bool {
+    raw.windows(LAST_TRADE_PRICE.len())
+        .any(|w| w == LAST_TRADE_PRICE)
+}
+
+fn strip_array_wrapper(raw: &[u8]) -> &[u8] {
+    // Skip leading '['
+    let start = 1;
+    // Find last ']' (might have trailing whitespace/newline)
+    let end = raw.iter().rposition(|&b| b == b']').unwrap_or(raw.len());
+    &raw[start..end]
+}
+
+fn parse_snapshot(raw: &[u8]) -> Option<Orderbook> {
+    match Orderbook::from_bytes(raw) {
+        Ok(ob) => Some(ob),
+        Err(e) => {
+            // Print first 500 chars of raw message to debug
+            let preview = String::from_utf8_lossy(&raw[..raw.len().min(500)]);
+            eprintln!("Parse error: {}\nRaw: {}", e, preview);
+            None
+        }
+    }
+}
+
+fn parse_price_change(raw: &[u8]) -> Option<Event> {
+    match IncomingPriceChangeMessage::from_bytes(raw) {
+        Ok(msg) => Some(Event::PriceChange {
+            changes: msg.price_changes,
+            hash: msg.hash,
+            timestamp: msg.timestamp,
+        }),
+        Err(e) => {
+            let preview = String::from_utf8_lossy(&raw[..raw.len().min(500)]);
+            eprintln!("Parse error: {}\nRaw: {}", e, preview);
+            None
+        }
+    }
+}
+
+fn parse_trade(raw: &[u8]) -> Option<Event> {
+    match IncomingLastTradePriceMessage::from_bytes(raw) {
+        Ok(msg) => Some(Event::Trade {
+            price: msg.price,
+            size: msg.size,
+            timestamp: msg.timestamp,
+        }),
+        Err(e) => {
+            let preview = String::from_utf8_lossy(&raw[..raw.len().min(500)]);
+            eprintln!("Parse error: {}\nRaw: {}", e, preview);
+            None
+        }
+    }
+}
+
+fn handle_events(
+    rx: Receiver<Event>,
+    orderbook: Arc<RwLock<Orderbook>>,
+    resubscribe_tx: Sender<()>,
+    asset_id: String,
+    mut recorder: Option<Recorder>,
+) {
+    let mut seq_no: u64 = 0;
+    let mut have_snapshot = false;
+    let mut resubscribing = false;
+    let mut candles = CandleStore::new(CANDLE_RESOLUTIONS.to_vec());
+
+    while let Ok(event) = rx.recv() {
+        if let Some(recorder) = recorder.as_mut() {
+            if let Err(e) = recorder.record(&event) {
+                eprintln!("⚠️  Failed to record event #{}: {}", seq_no, e);
+            }
+        }
+
+        let is_snapshot = matches!(event, Event::Snapshot(_));
+
+        let mut ob = orderbook.write();
+        let desynced = apply_event(
+            event,
+            &mut ob,
+            &mut candles,
+            &asset_id,
+            seq_no,
+            &mut have_snapshot,
+        );
+        drop(ob);
+
+        if is_snapshot {
+            resubscribing = false;
+        } else if desynced {
+            // Resubscribing is async; don't re-trigger it while we're
+            // already waiting on the fresh snapshot.
+            if !resubscribing {
+                resubscribing = true;
+                let _ = resubscribe_tx.send(());
+            }
+        }
+
+        seq_no += 1;
+    }
+}
+
+/// Applies one already-parsed [`Event`] to `orderbook`/`candles`, printing
+/// the same updates whether it's driven live by [`handle_events`] or offline
+/// by [`replay`]. Returns `true` if applying it revealed a checksum desync
+/// that calls for a fresh snapshot.
+fn apply_event(
+    event: Event,
+    orderbook: &mut Orderbook,
+    candles: &mut CandleStore,
+    asset_id: &str,
+    seq_no: u64,
+    have_snapshot: &mut bool,
+) -> bool {
+    match event {
+        Event::Snapshot(snapshot) => {
+            orderbook.apply_snapshot(snapshot);
+            *have_snapshot = true;
+            print_book_update(seq_no, orderbook);
+            false
+        }
+        Event::PriceChange { changes, hash, .. } => {
+            if !*have_snapshot {
+                eprintln!(
+                    "⚠️  Dropping price_change #{} received before initial snapshot",
+                    seq_no
+                );
+                return false;
+            }
+
+            orderbook.apply_price_changes(&changes);
+            orderbook.hash = hash;
+            print_book_update(seq_no, orderbook);
+
+            if let Err(mismatch) = orderbook.verify_checksum() {
+                eprintln!("🔴 {} — resyncing from a fresh snapshot", mismatch);
+                orderbook.clear();
+
+                // The book is empty until the fresh snapshot lands, so any
+                // price_change arriving in the meantime must be dropped
+                // rather than applied to an empty book — which would both
+                // corrupt the book and keep verify_checksum failing forever.
+                *have_snapshot = false;
+                return true;
+            }
+            false
+        }
+        Event::Trade {
+            price,
+            size,
+            timestamp,
+        } => {
+            for (resolution, closed) in
+                candles.apply_trade(&orderbook.market, asset_id, price, size, timestamp)
+            {
+                print_candle_update(seq_no, resolution, &closed);
+            }
+            false
+        }
+    }
+}
+
+fn print_book_update(seq_no: u64, ob: &Orderbook) {
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("📊 Orderbook Update #{}", seq_no);
+    println!("   Market: {}", ob.market);
+    println!("   Asset:  {}", &ob.asset_id[..20]);
+    println!("   Time:   {}", ob.timestamp);
+    if let Some((bid, bid_sz)) = ob.best_bid() {
+        println!("   Best Bid: {} (size: {})", bid, bid_sz);
+    }
+    if let Some((ask, ask_sz)) = ob.best_ask() {
+        println!("   Best Ask: {} (size: {})", ask, ask_sz);
+    }
+    if let Some(spread) = ob.spread() {
+        println!("   Spread:   {}", spread);
+    }
+}
+
+fn print_candle_update(seq_no: u64, resolution: Resolution, candle: &Candle) {
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("🕯️  Candle Closed #{} ({}s)", seq_no, resolution);
+    println!("   Open Time: {}", candle.open_time);
+    println!(
+        "   O: {} H: {} L: {} C: {} V: {}",
+        candle.open, candle.high, candle.low, candle.close, candle.volume
+    );
+}